@@ -28,34 +28,133 @@ macro_rules! xsignal {
     };
 }
 
+/// Calls a subr's C function pointer directly, bypassing `Ffuncall`, when
+/// `$funcraw` is `SUBRP` with fixed arity matching `$argraw`'s count.
+/// Yields `None` (for `call!`/`call_raw!` to fall back to `Ffuncall`)
+/// otherwise, e.g. for `MANY`/`UNEVALLED` subrs or non-subr callees.
+macro_rules! __direct_dispatch {
+    ($funcraw:expr, [$($argraw:expr),*]) => {{
+        #[allow(unused_unsafe)]
+        unsafe {
+            let funcraw = $funcraw;
+            if $crate::lisp::LispObject::from_raw(funcraw).is_subr() {
+                let subr = ::remacs_sys::XSUBR(funcraw);
+                const NARGS: ::libc::c_short = __direct_dispatch!(@count $($argraw),*) as ::libc::c_short;
+                if (*subr).min_args == NARGS && (*subr).max_args == NARGS {
+                    __direct_dispatch!(@call subr, [$($argraw),*])
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        }
+    }};
+    // `Lisp_Subr_Function` names a field per fixed arity (`a0`, `a1`, ...),
+    // matching upstream Emacs/XEmacs subr unions, rather than one generic
+    // slot reinterpreted for every arity.
+    (@call $subr:expr, []) => {{
+        let f: extern "C" fn() -> ::remacs_sys::Lisp_Object = ::std::mem::transmute((*$subr).function.a0);
+        Some($crate::lisp::LispObject::from_raw(f()))
+    }};
+    (@call $subr:expr, [$a0:expr]) => {{
+        let f: extern "C" fn(::remacs_sys::Lisp_Object) -> ::remacs_sys::Lisp_Object =
+            ::std::mem::transmute((*$subr).function.a1);
+        Some($crate::lisp::LispObject::from_raw(f($a0)))
+    }};
+    (@call $subr:expr, [$a0:expr, $a1:expr]) => {{
+        let f: extern "C" fn(::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object) -> ::remacs_sys::Lisp_Object =
+            ::std::mem::transmute((*$subr).function.a2);
+        Some($crate::lisp::LispObject::from_raw(f($a0, $a1)))
+    }};
+    (@call $subr:expr, [$a0:expr, $a1:expr, $a2:expr]) => {{
+        let f: extern "C" fn(::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object) -> ::remacs_sys::Lisp_Object =
+            ::std::mem::transmute((*$subr).function.a3);
+        Some($crate::lisp::LispObject::from_raw(f($a0, $a1, $a2)))
+    }};
+    (@call $subr:expr, [$a0:expr, $a1:expr, $a2:expr, $a3:expr]) => {{
+        let f: extern "C" fn(::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object) -> ::remacs_sys::Lisp_Object =
+            ::std::mem::transmute((*$subr).function.a4);
+        Some($crate::lisp::LispObject::from_raw(f($a0, $a1, $a2, $a3)))
+    }};
+    (@call $subr:expr, [$a0:expr, $a1:expr, $a2:expr, $a3:expr, $a4:expr]) => {{
+        let f: extern "C" fn(::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object) -> ::remacs_sys::Lisp_Object =
+            ::std::mem::transmute((*$subr).function.a5);
+        Some($crate::lisp::LispObject::from_raw(f($a0, $a1, $a2, $a3, $a4)))
+    }};
+    (@call $subr:expr, [$a0:expr, $a1:expr, $a2:expr, $a3:expr, $a4:expr, $a5:expr]) => {{
+        let f: extern "C" fn(::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object) -> ::remacs_sys::Lisp_Object =
+            ::std::mem::transmute((*$subr).function.a6);
+        Some($crate::lisp::LispObject::from_raw(f($a0, $a1, $a2, $a3, $a4, $a5)))
+    }};
+    (@call $subr:expr, [$a0:expr, $a1:expr, $a2:expr, $a3:expr, $a4:expr, $a5:expr, $a6:expr]) => {{
+        let f: extern "C" fn(::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object) -> ::remacs_sys::Lisp_Object =
+            ::std::mem::transmute((*$subr).function.a7);
+        Some($crate::lisp::LispObject::from_raw(f($a0, $a1, $a2, $a3, $a4, $a5, $a6)))
+    }};
+    (@call $subr:expr, [$a0:expr, $a1:expr, $a2:expr, $a3:expr, $a4:expr, $a5:expr, $a6:expr, $a7:expr]) => {{
+        let f: extern "C" fn(::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object) -> ::remacs_sys::Lisp_Object =
+            ::std::mem::transmute((*$subr).function.a8);
+        Some($crate::lisp::LispObject::from_raw(f($a0, $a1, $a2, $a3, $a4, $a5, $a6, $a7)))
+    }};
+    // More than 8 fixed args: no named union member to dispatch through,
+    // so fall back to `Ffuncall` like any non-eligible callee.
+    (@call $subr:expr, [$($argraw:expr),*]) => {
+        None
+    };
+    (@count) => { 0 };
+    (@count $a:expr $(, $rest:expr)*) => { 1 + __direct_dispatch!(@count $($rest),*) };
+}
+
 /// Macro to call Lisp functions with any number of arguments.
 /// Replaces call0, call1, etc. in the C layer.
 macro_rules! call {
     ($func:expr, $($arg:expr),*) => {{
-        let mut argsarray = [$func.to_raw(), $($arg.to_raw()),*];
+        let funcraw = $func.to_raw();
         #[allow(unused_unsafe)]
-        unsafe {
-            LispObject::from_raw(
-                ::remacs_sys::Ffuncall(argsarray.len() as ::libc::ptrdiff_t, argsarray.as_mut_ptr())
-            )
+        match __direct_dispatch!(funcraw, [$($arg.to_raw()),*]) {
+            Some(result) => result,
+            None => {
+                let mut argsarray = [funcraw, $($arg.to_raw()),*];
+                #[allow(unused_unsafe)]
+                unsafe {
+                    LispObject::from_raw(
+                        ::remacs_sys::Ffuncall(argsarray.len() as ::libc::ptrdiff_t, argsarray.as_mut_ptr())
+                    )
+                }
+            }
         }
     }}
 }
 
 macro_rules! call_raw {
     ($func:expr, $($arg:expr),*) => {{
-        let mut argsarray = [$func, $($arg),*];
+        let funcraw = $func;
         #[allow(unused_unsafe)]
-        unsafe {
-            LispObject::from_raw(
-                ::remacs_sys::Ffuncall(argsarray.len() as ::libc::ptrdiff_t, argsarray.as_mut_ptr())
-            )
+        match __direct_dispatch!(funcraw, [$($arg),*]) {
+            Some(result) => result,
+            None => {
+                let mut argsarray = [funcraw, $($arg),*];
+                #[allow(unused_unsafe)]
+                unsafe {
+                    LispObject::from_raw(
+                        ::remacs_sys::Ffuncall(argsarray.len() as ::libc::ptrdiff_t, argsarray.as_mut_ptr())
+                    )
+                }
+            }
         }
     }};
     ($func:expr) => {{
+        let mut funcraw = $func;
         #[allow(unused_unsafe)]
-        unsafe {
-            LispObject::from_raw(::remacs_sys::Ffuncall(1, &mut $func))
+        match __direct_dispatch!(funcraw, []) {
+            Some(result) => result,
+            None => {
+                #[allow(unused_unsafe)]
+                unsafe {
+                    LispObject::from_raw(::remacs_sys::Ffuncall(1, &mut funcraw))
+                }
+            }
         }
     }}
 }
@@ -112,6 +211,52 @@ macro_rules! wrong_type {
     };
 }
 
+/// Failure cases for a `defun_try!` body, each mapping onto one of the
+/// existing signal macros.
+pub enum LispError {
+    Signal(crate::lisp::LispObject, crate::lisp::LispObject),
+    WrongType(crate::lisp::LispObject, crate::lisp::LispObject),
+    Error(String),
+}
+
+/// Runs `body` (a `Result<LispObject, LispError>`) under `catch_unwind`
+/// and converts `Err`s and caught panics into the matching signal macro,
+/// so panics never unwind across the `extern "C"` boundary into the C core.
+macro_rules! defun_try {
+    ($body:expr) => {{
+        let result = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| $body));
+        match result {
+            Ok(Ok(value)) => value,
+            // xsignal!/wrong_type!/error! longjmp out via Fsignal and never
+            // actually reach the unreachable!() below, but each needs a
+            // trailing `!`-typed expression to unify this arm with `value`.
+            Ok(Err($crate::eval_macros::LispError::Signal(symbol, data))) => {
+                xsignal!(symbol, data);
+                unreachable!("xsignal! does not return")
+            }
+            Ok(Err($crate::eval_macros::LispError::WrongType(pred, obj))) => {
+                wrong_type!(pred.to_raw(), obj);
+                unreachable!("wrong_type! does not return")
+            }
+            Ok(Err($crate::eval_macros::LispError::Error(msg))) => {
+                error!("{}", msg);
+                unreachable!("error! does not return")
+            }
+            Err(panic) => {
+                let msg = if let Some(s) = panic.downcast_ref::<&str>() {
+                    (*s).to_string()
+                } else if let Some(s) = panic.downcast_ref::<String>() {
+                    s.clone()
+                } else {
+                    "unknown panic in Rust subr".to_string()
+                };
+                error!("{}", msg);
+                unreachable!("error! does not return")
+            }
+        }
+    }};
+}
+
 macro_rules! args_out_of_range {
     ($($tt:tt)+) => { xsignal!(::remacs_sys::Qargs_out_of_range, $($tt)+); };
 }
@@ -122,11 +267,140 @@ macro_rules! list {
     () => { $crate::lisp::LispObject::constant_nil() };
 }
 
-/// Macro that expands to nothing, but is used at build time to
-/// generate the starting symbol table. Equivalent to the DEFSYM
-/// macro. See also lib-src/make-docfile.c
+/// Resolves a possibly-absent C symbol via `dlsym`, caching the address
+/// in an atomic. Yields `None` instead of failing to link when the
+/// symbol isn't present in this build/version of Emacs.
+macro_rules! weak_fn {
+    ($name:expr, fn($($argty:ty),*) -> $ret:ty) => {{
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        const UNRESOLVED: usize = 1;
+
+        static ADDR: AtomicUsize = AtomicUsize::new(UNRESOLVED);
+
+        let mut addr = ADDR.load(Ordering::Relaxed);
+        if addr == UNRESOLVED {
+            #[allow(unused_unsafe)]
+            addr = unsafe {
+                ::libc::dlsym(::libc::RTLD_DEFAULT, concat!($name, "\0").as_ptr() as *const ::libc::c_char)
+                    as usize
+            };
+            ADDR.store(addr, Ordering::Relaxed);
+        }
+
+        if addr == 0 {
+            None
+        } else {
+            #[allow(unused_unsafe)]
+            unsafe {
+                Some(::std::mem::transmute::<usize, extern "C" fn($($argty),*) -> $ret>(addr))
+            }
+        }
+    }};
+}
+
+#[cfg(test)]
+mod weak_fn_tests {
+    #[test]
+    fn resolves_and_caches_a_real_symbol() {
+        // One expansion site, so both calls below share its cached address.
+        fn getpid_fn() -> Option<extern "C" fn() -> ::libc::pid_t> {
+            weak_fn!("getpid", fn() -> ::libc::pid_t)
+        }
+        let f = getpid_fn().expect("getpid is always present");
+        assert_eq!(f(), unsafe { ::libc::getpid() });
+
+        // Second call hits the cached address, not another dlsym lookup.
+        let getpid_again = getpid_fn().unwrap();
+        assert_eq!(getpid_again(), f());
+    }
+
+    #[test]
+    fn missing_symbol_resolves_to_none() {
+        let missing = weak_fn!("__remacs_no_such_symbol_xyz", fn() -> ::libc::c_int);
+        assert!(missing.is_none());
+    }
+}
+
+/// A lazily-interned `Q`-symbol. `init_syms` fills these in at startup;
+/// `def_lisp_sym!` declares one per symbol instead of a plain
+/// `LispObject` static, since the real value isn't known until interning
+/// runs.
+pub struct LispSymCell(::std::sync::atomic::AtomicUsize);
+
+impl LispSymCell {
+    pub const fn unset() -> Self {
+        LispSymCell(::std::sync::atomic::AtomicUsize::new(0))
+    }
+
+    fn set(&self, obj: crate::lisp::LispObject) {
+        self.0.store(obj.to_raw() as usize, ::std::sync::atomic::Ordering::Release);
+    }
+
+    pub fn get(&self) -> crate::lisp::LispObject {
+        let raw = self.0.load(::std::sync::atomic::Ordering::Acquire);
+        debug_assert!(raw != 0, "symbol read before init_syms() interned it");
+        crate::lisp::LispObject::from_raw(raw as ::remacs_sys::Lisp_Object)
+    }
+}
+
+/// One `def_lisp_sym!` registration, collected link-time into `LISP_SYMS`.
+/// `init_syms` walks the slice at startup and interns every entry.
+pub struct LispSymRegistration {
+    pub c_name: &'static str,
+    pub lisp_name: &'static str,
+    pub cell: &'static LispSymCell,
+}
+
+#[::linkme::distributed_slice]
+pub static LISP_SYMS: [LispSymRegistration] = [..];
+
+/// Declares the backing `Q`-symbol cell for `$name` and registers
+/// `($name, $value)` into `LISP_SYMS` for `init_syms` to intern at
+/// startup. Replaces the old make-docfile-scanned stub macro.
 macro_rules! def_lisp_sym {
-    ($name:expr, $value:expr) => {};
+    ($name:ident, $value:expr) => {
+        #[allow(non_upper_case_globals)]
+        pub static $name: $crate::eval_macros::LispSymCell = $crate::eval_macros::LispSymCell::unset();
+
+        $crate::paste::paste! {
+            #[::linkme::distributed_slice($crate::eval_macros::LISP_SYMS)]
+            static [<__SYM_ $name>]: $crate::eval_macros::LispSymRegistration =
+                $crate::eval_macros::LispSymRegistration {
+                    c_name: stringify!($name),
+                    lisp_name: $value,
+                    cell: &$name,
+                };
+
+            // Two `def_lisp_sym!` invocations for the same C identifier emit
+            // two `#[no_mangle]` statics under the same symbol name, so the
+            // linker rejects the build with a duplicate-symbol error instead
+            // of the collision only surfacing as a panic when `init_syms()`
+            // runs at Emacs startup.
+            #[no_mangle]
+            static [<__def_lisp_sym_dup_guard_ $name>]: u8 = 0;
+        }
+    };
+}
+
+/// Interns every symbol registered via `def_lisp_sym!` and writes the
+/// interned value back into its `$name` cell. Called once at startup in
+/// place of the old build-time symbol-table scan.
+pub fn init_syms() {
+    let mut seen = ::std::collections::HashSet::new();
+    for entry in LISP_SYMS.iter() {
+        if !seen.insert(entry.c_name) {
+            panic!("def_lisp_sym!({}, ..) registered more than once", entry.c_name);
+        }
+        #[allow(unused_unsafe)]
+        let raw = unsafe {
+            ::remacs_sys::intern_1(
+                entry.lisp_name.as_ptr() as *const ::libc::c_char,
+                entry.lisp_name.len() as ::libc::ptrdiff_t,
+            )
+        };
+        entry.cell.set(crate::lisp::LispObject::from_raw(raw));
+    }
 }
 
 #[allow(unused_macros)]
@@ -154,6 +428,73 @@ macro_rules! verify_lisp_type {
     };
 }
 
+/// Macro to call into Lisp while catching any signal raised during the
+/// call, instead of letting it longjmp past the calling Rust frames.
+///
+/// Wraps `body` in Emacs' `internal_condition_case_1` machinery: `handlers`
+/// is the condition (or list of conditions) to catch, e.g. `Qerror`. On
+/// success the expression evaluates to `Ok(LispObject)`; if a matching
+/// signal is raised, it evaluates to `Err((condition, data))` where
+/// `condition` and `data` are the car/cdr of the `(condition . data)` cons
+/// handed to the handler. `body` is boxed as a closure and passed to the
+/// trampoline through the single `Lisp_Object` argument, so it may use
+/// local variables. Because control crosses a `setjmp`/`longjmp`
+/// boundary, destructors of values live inside `body` do not run when a
+/// signal is caught.
+macro_rules! safe_call {
+    ($handlers:expr, $body:expr) => {{
+        use std::cell::Cell;
+
+        thread_local! {
+            static HANDLED: Cell<Option<(::remacs_sys::Lisp_Object, ::remacs_sys::Lisp_Object)>> =
+                Cell::new(None);
+        }
+
+        // `body`'s captures can't cross the `extern "C"` trampoline as a
+        // closure, so box it and thread it through via the single
+        // `Lisp_Object arg` slot `internal_condition_case_1` passes through
+        // unexamined, downcasting it back to the closure on the other side.
+        struct Capture<'a>(Option<Box<dyn FnOnce() -> $crate::lisp::LispObject + 'a>>);
+
+        extern "C" fn bfun(arg: ::remacs_sys::Lisp_Object) -> ::remacs_sys::Lisp_Object {
+            #[allow(unused_unsafe)]
+            let capture = unsafe { &mut *(arg as *mut Capture) };
+            let body = capture.0.take().expect("safe_call! body invoked twice");
+            body().to_raw()
+        }
+
+        extern "C" fn hfun(err: ::remacs_sys::Lisp_Object) -> ::remacs_sys::Lisp_Object {
+            #[allow(unused_unsafe)]
+            let condition = unsafe { ::remacs_sys::Fcar(err) };
+            #[allow(unused_unsafe)]
+            let data = unsafe { ::remacs_sys::Fcdr(err) };
+            HANDLED.with(|h| h.set(Some((condition, data))));
+            ::remacs_sys::Qnil
+        }
+
+        let mut capture = Capture(Some(Box::new(|| $body)));
+        let arg = &mut capture as *mut Capture as ::remacs_sys::Lisp_Object;
+
+        #[allow(unused_unsafe)]
+        let result = unsafe {
+            ::remacs_sys::internal_condition_case_1(
+                bfun,
+                arg,
+                $handlers,
+                hfun,
+            )
+        };
+
+        match HANDLED.with(|h| h.take()) {
+            Some((condition, data)) => Err((
+                $crate::lisp::LispObject::from_raw(condition),
+                $crate::lisp::LispObject::from_raw(data),
+            )),
+            None => Ok($crate::lisp::LispObject::from_raw(result)),
+        }
+    }};
+}
+
 /// Get the index of `ident` into buffer's `local_flags` array. This
 /// value will be stored in the variable `buffer_local_flags` of type
 /// buffer